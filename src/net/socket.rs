@@ -2,17 +2,25 @@ use crate::{
     io,
     net::{SocketAddr, SocketAddrUnix, SocketAddrV4, SocketAddrV6},
 };
-#[cfg(any(linux_raw, all(libc, not(any(target_os = "ios", target_os = "macos")))))]
+#[cfg(any(linux_raw, libc))]
 use bitflags::bitflags;
 use io_lifetimes::{AsFd, BorrowedFd, OwnedFd};
-use std::mem::{size_of, MaybeUninit};
+use std::mem::{size_of, size_of_val, MaybeUninit};
 use std::os::raw::c_int;
 #[cfg(linux_raw)]
 use std::os::raw::c_uint;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::time::Duration;
+#[cfg(linux_raw)]
+use linux_raw_sys::general as c;
+#[cfg(linux_raw)]
+use linux_raw_sys::general::{sockaddr, sockaddr_storage, socklen_t};
+#[cfg(libc)]
+use libc as c;
 #[cfg(libc)]
 use {
     crate::{negone_err, zero_ok},
-    libc::{sockaddr_storage, socklen_t},
+    libc::{sockaddr, sockaddr_storage, socklen_t},
     unsafe_io::os::posish::{AsRawFd, FromRawFd},
 };
 
@@ -84,6 +92,14 @@ pub enum AddressFamily {
         target_os = "netbsd"
     )))]
     Netlink = libc::AF_NETLINK as u32,
+
+    /// `AF_PACKET`
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    Packet = libc::AF_PACKET as u32,
+
+    /// `AF_VSOCK`
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    Vsock = libc::AF_VSOCK as u32,
 }
 
 /// `AF_*` constants.
@@ -104,6 +120,12 @@ pub enum AddressFamily {
 
     /// `AF_NETLINK`
     Netlink = linux_raw_sys::general::AF_NETLINK,
+
+    /// `AF_PACKET`
+    Packet = linux_raw_sys::general::AF_PACKET,
+
+    /// `AF_VSOCK`
+    Vsock = linux_raw_sys::general::AF_VSOCK,
 }
 
 /// `IPPROTO_*`
@@ -301,6 +323,99 @@ bitflags! {
     }
 }
 
+#[cfg(all(libc, not(any(target_os = "ios", target_os = "macos"))))]
+bitflags! {
+    /// `SOCK_*` constants for `socketpair`.
+    pub struct SocketFlags: c_int {
+        /// `SOCK_NONBLOCK`
+        const NONBLOCK = libc::SOCK_NONBLOCK;
+        /// `SOCK_CLOEXEC`
+        const CLOEXEC = libc::SOCK_CLOEXEC;
+    }
+}
+
+#[cfg(linux_raw)]
+bitflags! {
+    /// `SOCK_*` constants for `socketpair`.
+    pub struct SocketFlags: c_uint {
+        /// `SOCK_NONBLOCK`
+        const NONBLOCK = linux_raw_sys::general::O_NONBLOCK;
+        /// `SOCK_CLOEXEC`
+        const CLOEXEC = linux_raw_sys::general::O_CLOEXEC;
+    }
+}
+
+#[cfg(libc)]
+bitflags! {
+    /// `MSG_*` constants for `sendto`.
+    pub struct SendFlags: c_int {
+        /// `MSG_CONFIRM`
+        #[cfg(not(any(target_os = "ios", target_os = "macos")))]
+        const CONFIRM = libc::MSG_CONFIRM;
+        /// `MSG_DONTWAIT`
+        const DONTWAIT = libc::MSG_DONTWAIT;
+        /// `MSG_MORE`
+        #[cfg(not(any(target_os = "ios", target_os = "macos")))]
+        const MORE = libc::MSG_MORE;
+        /// `MSG_NOSIGNAL`
+        #[cfg(not(any(target_os = "ios", target_os = "macos")))]
+        const NOSIGNAL = libc::MSG_NOSIGNAL;
+        /// `MSG_OOB`
+        const OOB = libc::MSG_OOB;
+    }
+}
+
+#[cfg(linux_raw)]
+bitflags! {
+    /// `MSG_*` constants for `sendto`.
+    pub struct SendFlags: c_uint {
+        /// `MSG_CONFIRM`
+        const CONFIRM = linux_raw_sys::general::MSG_CONFIRM;
+        /// `MSG_DONTWAIT`
+        const DONTWAIT = linux_raw_sys::general::MSG_DONTWAIT;
+        /// `MSG_MORE`
+        const MORE = linux_raw_sys::general::MSG_MORE;
+        /// `MSG_NOSIGNAL`
+        const NOSIGNAL = linux_raw_sys::general::MSG_NOSIGNAL;
+        /// `MSG_OOB`
+        const OOB = linux_raw_sys::general::MSG_OOB;
+    }
+}
+
+#[cfg(libc)]
+bitflags! {
+    /// `MSG_*` constants for `recvfrom`.
+    pub struct RecvFlags: c_int {
+        /// `MSG_DONTWAIT`
+        const DONTWAIT = libc::MSG_DONTWAIT;
+        /// `MSG_OOB`
+        const OOB = libc::MSG_OOB;
+        /// `MSG_PEEK`
+        const PEEK = libc::MSG_PEEK;
+        /// `MSG_TRUNC`
+        const TRUNC = libc::MSG_TRUNC;
+        /// `MSG_WAITALL`
+        const WAITALL = libc::MSG_WAITALL;
+    }
+}
+
+#[cfg(linux_raw)]
+bitflags! {
+    /// `MSG_*` constants for `recvfrom`.
+    pub struct RecvFlags: c_uint {
+        /// `MSG_DONTWAIT`
+        const DONTWAIT = linux_raw_sys::general::MSG_DONTWAIT;
+        /// `MSG_OOB`
+        const OOB = linux_raw_sys::general::MSG_OOB;
+        /// `MSG_PEEK`
+        const PEEK = linux_raw_sys::general::MSG_PEEK;
+        /// `MSG_TRUNC`
+        const TRUNC = linux_raw_sys::general::MSG_TRUNC;
+        /// `MSG_WAITALL`
+        const WAITALL = linux_raw_sys::general::MSG_WAITALL;
+    }
+}
+
 /// `socket(domain, type_, protocol)`
 #[inline]
 pub fn socket(domain: AddressFamily, type_: SocketType, protocol: Protocol) -> io::Result<OwnedFd> {
@@ -324,266 +439,776 @@ fn _socket(domain: AddressFamily, type_: SocketType, protocol: Protocol) -> io::
     crate::linux_raw::socket(domain as c_uint, type_ as c_uint, protocol as c_uint)
 }
 
-/// `bind(sockfd, addr, sizeof(struct sockaddr_un))`
+/// `socketpair(domain, type_ | flags, protocol, sv)`
+#[cfg(not(any(target_os = "ios", target_os = "macos")))]
 #[inline]
-pub fn bind_un<'f, Fd: AsFd<'f>>(sockfd: Fd, addr: &SocketAddrUnix) -> io::Result<()> {
-    let sockfd = sockfd.as_fd();
-    _bind_un(sockfd, addr)
+pub fn socketpair(
+    domain: AddressFamily,
+    type_: SocketType,
+    flags: SocketFlags,
+    protocol: Protocol,
+) -> io::Result<(OwnedFd, OwnedFd)> {
+    _socketpair(domain, type_, flags, protocol)
 }
 
-#[cfg(libc)]
-fn _bind_un(sockfd: BorrowedFd<'_>, addr: &SocketAddrUnix) -> io::Result<()> {
+#[cfg(all(libc, not(any(target_os = "ios", target_os = "macos"))))]
+fn _socketpair(
+    domain: AddressFamily,
+    type_: SocketType,
+    flags: SocketFlags,
+    protocol: Protocol,
+) -> io::Result<(OwnedFd, OwnedFd)> {
     unsafe {
-        zero_ok(libc::bind(
-            sockfd.as_raw_fd(),
-            addr as *const _ as *const _,
-            size_of::<SocketAddrUnix>() as socklen_t,
-        ))
+        let mut fds = MaybeUninit::<[c_int; 2]>::uninit();
+        zero_ok(libc::socketpair(
+            domain as c_int,
+            type_ as c_int | flags.bits(),
+            protocol as c_int,
+            fds.as_mut_ptr().cast::<c_int>(),
+        ))?;
+        let fds = fds.assume_init();
+        Ok((OwnedFd::from_raw_fd(fds[0]), OwnedFd::from_raw_fd(fds[1])))
     }
 }
 
 #[cfg(linux_raw)]
-fn _bind_un(sockfd: BorrowedFd<'_>, addr: &SocketAddrUnix) -> io::Result<()> {
-    crate::linux_raw::bind_un(sockfd, addr)
+fn _socketpair(
+    domain: AddressFamily,
+    type_: SocketType,
+    flags: SocketFlags,
+    protocol: Protocol,
+) -> io::Result<(OwnedFd, OwnedFd)> {
+    crate::linux_raw::socketpair(
+        domain as c_uint,
+        type_ as c_uint,
+        flags.bits(),
+        protocol as c_uint,
+    )
 }
 
-/// `bind(sockfd, addr, sizeof(struct sockaddr_in))`
+/// A socket address that can be passed to `bind` or `connect`.
+///
+/// This is implemented by the concrete `SocketAddr*` types, exposing the raw
+/// `sockaddr` pointer and length the system calls expect, so that a single
+/// generic entry point can dispatch on the address family.
+pub trait SockaddrLike {
+    /// Return a pointer to the `sockaddr` representation of this address.
+    fn as_ptr(&self) -> *const sockaddr;
+
+    /// Return the length of this address, in bytes.
+    fn socklen(&self) -> socklen_t;
+}
+
+impl SockaddrLike for SocketAddrUnix {
+    #[inline]
+    fn as_ptr(&self) -> *const sockaddr {
+        self as *const _ as *const sockaddr
+    }
+
+    #[inline]
+    fn socklen(&self) -> socklen_t {
+        size_of::<SocketAddrUnix>() as socklen_t
+    }
+}
+
+impl SockaddrLike for SocketAddrV4 {
+    #[inline]
+    fn as_ptr(&self) -> *const sockaddr {
+        self as *const _ as *const sockaddr
+    }
+
+    #[inline]
+    fn socklen(&self) -> socklen_t {
+        size_of::<SocketAddrV4>() as socklen_t
+    }
+}
+
+impl SockaddrLike for SocketAddrV6 {
+    #[inline]
+    fn as_ptr(&self) -> *const sockaddr {
+        self as *const _ as *const sockaddr
+    }
+
+    #[inline]
+    fn socklen(&self) -> socklen_t {
+        size_of::<SocketAddrV6>() as socklen_t
+    }
+}
+
+/// A link-layer address, wrapping `sockaddr_ll` (`AF_PACKET`).
+#[cfg(any(linux_raw, all(libc, any(target_os = "android", target_os = "linux"))))]
+#[derive(Clone)]
+#[repr(transparent)]
+pub struct SocketAddrLink {
+    inner: c::sockaddr_ll,
+}
+
+#[cfg(any(linux_raw, all(libc, any(target_os = "android", target_os = "linux"))))]
+impl SocketAddrLink {
+    /// Construct a link-layer address binding `protocol` on the interface with
+    /// index `ifindex`, with the given hardware type, packet type, and
+    /// hardware address.
+    pub fn new(
+        protocol: u16,
+        ifindex: u32,
+        hatype: u16,
+        pkttype: u8,
+        halen: u8,
+        addr: [u8; 8],
+    ) -> Self {
+        let mut inner: c::sockaddr_ll = unsafe { std::mem::zeroed() };
+        inner.sll_family = c::AF_PACKET as _;
+        inner.sll_protocol = protocol.to_be();
+        inner.sll_ifindex = ifindex as _;
+        inner.sll_hatype = hatype;
+        inner.sll_pkttype = pkttype;
+        inner.sll_halen = halen;
+        inner.sll_addr = addr;
+        Self { inner }
+    }
+
+    /// The interface index this address refers to.
+    #[inline]
+    pub fn ifindex(&self) -> u32 {
+        self.inner.sll_ifindex as u32
+    }
+
+    /// The ARP hardware type (`ARPHRD_*`).
+    #[inline]
+    pub fn hatype(&self) -> u16 {
+        self.inner.sll_hatype
+    }
+
+    /// The packet type (`PACKET_*`).
+    #[inline]
+    pub fn pkttype(&self) -> u8 {
+        self.inner.sll_pkttype
+    }
+
+    /// The hardware (e.g. Ethernet MAC) address bytes.
+    #[inline]
+    pub fn hardware_addr(&self) -> &[u8] {
+        &self.inner.sll_addr[..self.inner.sll_halen as usize]
+    }
+}
+
+#[cfg(any(linux_raw, all(libc, any(target_os = "android", target_os = "linux"))))]
+impl SockaddrLike for SocketAddrLink {
+    #[inline]
+    fn as_ptr(&self) -> *const sockaddr {
+        &self.inner as *const _ as *const sockaddr
+    }
+
+    #[inline]
+    fn socklen(&self) -> socklen_t {
+        size_of::<c::sockaddr_ll>() as socklen_t
+    }
+}
+
+/// A VM socket address, wrapping `sockaddr_vm` (`AF_VSOCK`).
+#[cfg(any(linux_raw, all(libc, any(target_os = "android", target_os = "linux"))))]
+#[derive(Clone)]
+#[repr(transparent)]
+pub struct SocketAddrVsock {
+    inner: c::sockaddr_vm,
+}
+
+#[cfg(any(linux_raw, all(libc, any(target_os = "android", target_os = "linux"))))]
+impl SocketAddrVsock {
+    /// Construct a vsock address for the given context id and port.
+    pub fn new(cid: u32, port: u32) -> Self {
+        let mut inner: c::sockaddr_vm = unsafe { std::mem::zeroed() };
+        inner.svm_family = c::AF_VSOCK as _;
+        inner.svm_cid = cid;
+        inner.svm_port = port;
+        Self { inner }
+    }
+
+    /// The context id (CID) of this address.
+    #[inline]
+    pub fn cid(&self) -> u32 {
+        self.inner.svm_cid
+    }
+
+    /// The port of this address.
+    #[inline]
+    pub fn port(&self) -> u32 {
+        self.inner.svm_port
+    }
+}
+
+#[cfg(any(linux_raw, all(libc, any(target_os = "android", target_os = "linux"))))]
+impl SockaddrLike for SocketAddrVsock {
+    #[inline]
+    fn as_ptr(&self) -> *const sockaddr {
+        &self.inner as *const _ as *const sockaddr
+    }
+
+    #[inline]
+    fn socklen(&self) -> socklen_t {
+        size_of::<c::sockaddr_vm>() as socklen_t
+    }
+}
+
+/// Owned storage for an address returned by `accept`, `accept4`, or
+/// `recvfrom`, wrapping a `sockaddr_storage` and its length.
+///
+/// The `as_*` accessors validate `ss_family` and the reported length, so an
+/// unexpected or truncated address is a recoverable error rather than an
+/// abort.
+pub struct SocketAddrStorage {
+    storage: sockaddr_storage,
+    len: socklen_t,
+}
+
+impl SocketAddrStorage {
+    /// Wrap a raw `sockaddr_storage` and its length.
+    #[inline]
+    fn new(storage: sockaddr_storage, len: socklen_t) -> Self {
+        Self { storage, len }
+    }
+
+    /// The raw address family of the stored address.
+    #[cfg(libc)]
+    #[inline]
+    fn family(&self) -> i32 {
+        i32::from(self.storage.ss_family)
+    }
+
+    /// The raw address family of the stored address.
+    #[cfg(linux_raw)]
+    #[inline]
+    fn family(&self) -> u32 {
+        u32::from(self.storage.ss_family)
+    }
+
+    /// Interpret the address as an `AF_LOCAL` address, if that is its family.
+    #[cfg(libc)]
+    pub fn as_unix(&self) -> Option<SocketAddrUnix> {
+        // The kernel reports the in-use length, which for `AF_LOCAL` is
+        // `offsetof(sockaddr_un, sun_path)` for an unnamed peer and only as
+        // much of `sun_path` as is occupied otherwise — far below the full
+        // struct size. Gate on the family plus the size of the `ss_family`
+        // field, which equals that minimal prefix on Linux and Android; on
+        // targets whose `sockaddr_un` starts with a `sun_len` byte the prefix
+        // is one byte longer than this check.
+        if self.family() == libc::AF_LOCAL
+            && self.len as usize >= size_of_val(&self.storage.ss_family)
+        {
+            Some(unsafe { (*(&self.storage as *const _ as *const SocketAddrUnix)).clone() })
+        } else {
+            None
+        }
+    }
+
+    /// Interpret the address as an `AF_LOCAL` address, if that is its family.
+    #[cfg(linux_raw)]
+    pub fn as_unix(&self) -> Option<SocketAddrUnix> {
+        // See the `libc` implementation: the reported length for `AF_LOCAL` is
+        // `offsetof(sun_path)` plus the occupied path bytes, not the full
+        // `sockaddr_un`, so gate on the family plus the `ss_family` prefix.
+        if self.family() == linux_raw_sys::general::AF_LOCAL
+            && self.len as usize >= size_of_val(&self.storage.ss_family)
+        {
+            Some(unsafe { (*(&self.storage as *const _ as *const SocketAddrUnix)).clone() })
+        } else {
+            None
+        }
+    }
+
+    /// Interpret the address as an `AF_INET` address, if that is its family.
+    #[cfg(libc)]
+    pub fn as_v4(&self) -> Option<SocketAddrV4> {
+        if self.family() == libc::AF_INET && self.len as usize >= size_of::<SocketAddrV4>() {
+            Some(unsafe { (*(&self.storage as *const _ as *const SocketAddrV4)).clone() })
+        } else {
+            None
+        }
+    }
+
+    /// Interpret the address as an `AF_INET` address, if that is its family.
+    #[cfg(linux_raw)]
+    pub fn as_v4(&self) -> Option<SocketAddrV4> {
+        if self.family() == linux_raw_sys::general::AF_INET
+            && self.len as usize >= size_of::<SocketAddrV4>()
+        {
+            Some(unsafe { (*(&self.storage as *const _ as *const SocketAddrV4)).clone() })
+        } else {
+            None
+        }
+    }
+
+    /// Interpret the address as an `AF_INET6` address, if that is its family.
+    #[cfg(libc)]
+    pub fn as_v6(&self) -> Option<SocketAddrV6> {
+        if self.family() == libc::AF_INET6 && self.len as usize >= size_of::<SocketAddrV6>() {
+            Some(unsafe { (*(&self.storage as *const _ as *const SocketAddrV6)).clone() })
+        } else {
+            None
+        }
+    }
+
+    /// Interpret the address as an `AF_INET6` address, if that is its family.
+    #[cfg(linux_raw)]
+    pub fn as_v6(&self) -> Option<SocketAddrV6> {
+        if self.family() == linux_raw_sys::general::AF_INET6
+            && self.len as usize >= size_of::<SocketAddrV6>()
+        {
+            Some(unsafe { (*(&self.storage as *const _ as *const SocketAddrV6)).clone() })
+        } else {
+            None
+        }
+    }
+
+    /// Interpret the address as an `AF_PACKET` link-layer address, if that is
+    /// its family.
+    #[cfg(any(linux_raw, all(libc, any(target_os = "android", target_os = "linux"))))]
+    pub fn as_link(&self) -> Option<SocketAddrLink> {
+        if self.family() == c::AF_PACKET as _ && self.len as usize >= size_of::<c::sockaddr_ll>() {
+            Some(unsafe { (*(&self.storage as *const _ as *const SocketAddrLink)).clone() })
+        } else {
+            None
+        }
+    }
+
+    /// Interpret the address as an `AF_VSOCK` address, if that is its family.
+    #[cfg(any(linux_raw, all(libc, any(target_os = "android", target_os = "linux"))))]
+    pub fn as_vsock(&self) -> Option<SocketAddrVsock> {
+        if self.family() == c::AF_VSOCK as _ && self.len as usize >= size_of::<c::sockaddr_vm>() {
+            Some(unsafe { (*(&self.storage as *const _ as *const SocketAddrVsock)).clone() })
+        } else {
+            None
+        }
+    }
+
+    /// Clone the address behind a raw `sockaddr` pointer into owned storage,
+    /// copying only as many bytes as the address family requires. Returns
+    /// `None` for a null pointer.
+    #[cfg(libc)]
+    unsafe fn clone_raw(ptr: *const sockaddr) -> Option<Self> {
+        if ptr.is_null() {
+            return None;
+        }
+        let len = match i32::from((*ptr).sa_family) {
+            libc::AF_INET => size_of::<libc::sockaddr_in>(),
+            libc::AF_INET6 => size_of::<libc::sockaddr_in6>(),
+            libc::AF_LOCAL => size_of::<libc::sockaddr_un>(),
+            #[cfg(any(target_os = "android", target_os = "linux"))]
+            libc::AF_PACKET => size_of::<libc::sockaddr_ll>(),
+            #[cfg(any(target_os = "android", target_os = "linux"))]
+            libc::AF_VSOCK => size_of::<libc::sockaddr_vm>(),
+            _ => size_of::<sockaddr>(),
+        }
+        .min(size_of::<sockaddr_storage>());
+        let mut storage = MaybeUninit::<sockaddr_storage>::zeroed();
+        std::ptr::copy_nonoverlapping(ptr as *const u8, storage.as_mut_ptr() as *mut u8, len);
+        Some(Self::new(storage.assume_init(), len as socklen_t))
+    }
+
+    /// Decode the stored address into a `SocketAddr`, returning `EINVAL` for any
+    /// other address family, or for an address shorter than its family needs.
+    ///
+    /// This covers the families `SocketAddr` can represent (`AF_INET`,
+    /// `AF_INET6`, `AF_LOCAL`). The lower-level Linux families are reached
+    /// through [`as_link`](Self::as_link) and [`as_vsock`](Self::as_vsock)
+    /// instead, since `SocketAddr` has no variant for them.
+    pub fn decode(&self) -> io::Result<SocketAddr> {
+        if let Some(addr) = self.as_v4() {
+            Ok(SocketAddr::V4(addr))
+        } else if let Some(addr) = self.as_v6() {
+            Ok(SocketAddr::V6(addr))
+        } else if let Some(addr) = self.as_unix() {
+            Ok(SocketAddr::Unix(addr))
+        } else {
+            Err(io::Error::INVAL)
+        }
+    }
+}
+
+/// `bind(sockfd, addr, addrlen)`
 #[inline]
-pub fn bind_in<'f, Fd: AsFd<'f>>(sockfd: Fd, addr: &SocketAddrV4) -> io::Result<()> {
+pub fn bind<'f, Fd: AsFd<'f>>(sockfd: Fd, addr: &impl SockaddrLike) -> io::Result<()> {
     let sockfd = sockfd.as_fd();
-    _bind_in(sockfd, addr)
+    _bind(sockfd, addr.as_ptr(), addr.socklen())
 }
 
 #[cfg(libc)]
-fn _bind_in(sockfd: BorrowedFd<'_>, addr: &SocketAddrV4) -> io::Result<()> {
-    unsafe {
-        zero_ok(libc::bind(
-            sockfd.as_raw_fd(),
-            addr as *const _ as *const _,
-            size_of::<SocketAddrV4>() as socklen_t,
-        ))
-    }
+fn _bind(sockfd: BorrowedFd<'_>, addr: *const sockaddr, len: socklen_t) -> io::Result<()> {
+    unsafe { zero_ok(libc::bind(sockfd.as_raw_fd(), addr, len)) }
 }
 
 #[cfg(linux_raw)]
-fn _bind_in(sockfd: BorrowedFd<'_>, addr: &SocketAddrV4) -> io::Result<()> {
-    crate::linux_raw::bind_in(sockfd, addr)
+fn _bind(sockfd: BorrowedFd<'_>, addr: *const sockaddr, len: socklen_t) -> io::Result<()> {
+    crate::linux_raw::bind(sockfd, addr, len)
+}
+
+/// `bind(sockfd, addr, sizeof(struct sockaddr_un))`
+#[inline]
+pub fn bind_un<'f, Fd: AsFd<'f>>(sockfd: Fd, addr: &SocketAddrUnix) -> io::Result<()> {
+    bind(sockfd, addr)
+}
+
+/// `bind(sockfd, addr, sizeof(struct sockaddr_in))`
+#[inline]
+pub fn bind_in<'f, Fd: AsFd<'f>>(sockfd: Fd, addr: &SocketAddrV4) -> io::Result<()> {
+    bind(sockfd, addr)
 }
 
 /// `bind(sockfd, addr, sizeof(struct sockaddr_in6))`
 #[inline]
 pub fn bind_in6<'f, Fd: AsFd<'f>>(sockfd: Fd, addr: &SocketAddrV6) -> io::Result<()> {
+    bind(sockfd, addr)
+}
+
+/// `connect(sockfd, addr, addrlen)`
+#[inline]
+pub fn connect<'f, Fd: AsFd<'f>>(sockfd: Fd, addr: &impl SockaddrLike) -> io::Result<()> {
+    let sockfd = sockfd.as_fd();
+    _connect(sockfd, addr.as_ptr(), addr.socklen())
+}
+
+#[cfg(libc)]
+fn _connect(sockfd: BorrowedFd<'_>, addr: *const sockaddr, len: socklen_t) -> io::Result<()> {
+    unsafe { zero_ok(libc::connect(sockfd.as_raw_fd(), addr, len)) }
+}
+
+#[cfg(linux_raw)]
+fn _connect(sockfd: BorrowedFd<'_>, addr: *const sockaddr, len: socklen_t) -> io::Result<()> {
+    crate::linux_raw::connect(sockfd, addr, len)
+}
+
+/// `connect(sockfd, addr, sizeof(struct sockaddr_un))`
+#[inline]
+pub fn connect_un<'f, Fd: AsFd<'f>>(sockfd: Fd, addr: &SocketAddrUnix) -> io::Result<()> {
+    connect(sockfd, addr)
+}
+
+/// `connect(sockfd, addr, sizeof(struct sockaddr_in))`
+#[inline]
+pub fn connect_in<'f, Fd: AsFd<'f>>(sockfd: Fd, addr: &SocketAddrV4) -> io::Result<()> {
+    connect(sockfd, addr)
+}
+
+/// `connect(sockfd, addr, sizeof(struct sockaddr_in6))`
+#[inline]
+pub fn connect_in6<'f, Fd: AsFd<'f>>(sockfd: Fd, addr: &SocketAddrV6) -> io::Result<()> {
+    connect(sockfd, addr)
+}
+
+/// `listen(fd, backlog)`
+#[inline]
+pub fn listen<'f, Fd: AsFd<'f>>(sockfd: Fd, backlog: c_int) -> io::Result<()> {
     let sockfd = sockfd.as_fd();
-    _bind_in6(sockfd, addr)
+    _listen(sockfd, backlog)
 }
 
 #[cfg(libc)]
-fn _bind_in6(sockfd: BorrowedFd<'_>, addr: &SocketAddrV6) -> io::Result<()> {
+fn _listen(sockfd: BorrowedFd<'_>, backlog: c_int) -> io::Result<()> {
+    unsafe { zero_ok(libc::listen(sockfd.as_raw_fd(), backlog)) }
+}
+
+#[cfg(linux_raw)]
+#[inline]
+fn _listen(sockfd: BorrowedFd<'_>, backlog: c_int) -> io::Result<()> {
+    crate::linux_raw::listen(sockfd, backlog)
+}
+
+/// `accept(fd, addr, len)`
+///
+/// The peer address is returned as a [`SocketAddrStorage`] so that every
+/// address family is representable; call [`SocketAddrStorage::decode`] for a
+/// `SocketAddr` covering `AF_INET`/`AF_INET6`/`AF_LOCAL`.
+#[cfg(any(target_os = "ios", target_os = "macos"))]
+#[inline]
+pub fn accept<'f, Fd: AsFd<'f>>(sockfd: Fd) -> io::Result<(OwnedFd, SocketAddrStorage)> {
+    let sockfd = sockfd.as_fd();
+    _accept(sockfd)
+}
+
+#[cfg(all(libc, any(target_os = "ios", target_os = "macos")))]
+fn _accept(sockfd: BorrowedFd<'_>) -> io::Result<(OwnedFd, SocketAddrStorage)> {
     unsafe {
-        zero_ok(libc::bind(
+        let mut storage = MaybeUninit::<sockaddr_storage>::uninit();
+        let mut len = size_of::<sockaddr_storage>() as socklen_t;
+        let raw_fd = negone_err(libc::accept(
             sockfd.as_raw_fd(),
-            addr as *const _ as *const _,
-            size_of::<SocketAddrV6>() as socklen_t,
-        ))
+            storage.as_mut_ptr() as *mut _,
+            &mut len,
+        ))?;
+        let owned_fd = OwnedFd::from_raw_fd(raw_fd);
+        Ok((owned_fd, SocketAddrStorage::new(storage.assume_init(), len)))
+    }
+}
+
+/// `accept4(fd, addr, len, flags)`
+///
+/// Like [`accept`], the peer address is returned as a [`SocketAddrStorage`];
+/// use [`SocketAddrStorage::decode`] to recover a `SocketAddr`.
+#[cfg(not(any(target_os = "ios", target_os = "macos")))]
+#[inline]
+pub fn accept4<'f, Fd: AsFd<'f>>(
+    sockfd: Fd,
+    flags: AcceptFlags,
+) -> io::Result<(OwnedFd, SocketAddrStorage)> {
+    let sockfd = sockfd.as_fd();
+    _accept4(sockfd, flags)
+}
+
+#[cfg(all(libc, not(any(target_os = "ios", target_os = "macos"))))]
+fn _accept4(sockfd: BorrowedFd<'_>, flags: AcceptFlags) -> io::Result<(OwnedFd, SocketAddrStorage)> {
+    unsafe {
+        let mut storage = MaybeUninit::<sockaddr_storage>::uninit();
+        let mut len = size_of::<sockaddr_storage>() as socklen_t;
+        let raw_fd = negone_err(libc::accept4(
+            sockfd.as_raw_fd(),
+            storage.as_mut_ptr() as *mut _,
+            &mut len,
+            flags.bits(),
+        ))?;
+        let owned_fd = OwnedFd::from_raw_fd(raw_fd);
+        Ok((owned_fd, SocketAddrStorage::new(storage.assume_init(), len)))
     }
 }
 
 #[cfg(linux_raw)]
-fn _bind_in6(sockfd: BorrowedFd<'_>, addr: &SocketAddrV6) -> io::Result<()> {
-    crate::linux_raw::bind_in6(sockfd, addr)
+#[inline]
+fn _accept4(sockfd: BorrowedFd<'_>, flags: AcceptFlags) -> io::Result<(OwnedFd, SocketAddrStorage)> {
+    let (owned_fd, storage, len) = crate::linux_raw::accept4(sockfd, flags.bits())?;
+    Ok((owned_fd, SocketAddrStorage::new(storage, len)))
 }
 
-/// `connect(sockfd, addr, sizeof(struct sockaddr_un))`
+/// `sendto(fd, buf, flags, addr, sizeof(struct sockaddr_un))`
 #[inline]
-pub fn connect_un<'f, Fd: AsFd<'f>>(sockfd: Fd, addr: &SocketAddrUnix) -> io::Result<()> {
+pub fn sendto_un<'f, Fd: AsFd<'f>>(
+    sockfd: Fd,
+    buf: &[u8],
+    flags: SendFlags,
+    addr: &SocketAddrUnix,
+) -> io::Result<usize> {
     let sockfd = sockfd.as_fd();
-    _connect_un(sockfd, addr)
+    _sendto_un(sockfd, buf, flags, addr)
 }
 
 #[cfg(libc)]
-fn _connect_un(sockfd: BorrowedFd<'_>, addr: &SocketAddrUnix) -> io::Result<()> {
+fn _sendto_un(
+    sockfd: BorrowedFd<'_>,
+    buf: &[u8],
+    flags: SendFlags,
+    addr: &SocketAddrUnix,
+) -> io::Result<usize> {
     unsafe {
-        zero_ok(libc::connect(
+        let nsent = negone_err(libc::sendto(
             sockfd.as_raw_fd(),
+            buf.as_ptr().cast(),
+            buf.len(),
+            flags.bits(),
             addr as *const _ as *const _,
             size_of::<SocketAddrUnix>() as socklen_t,
-        ))
+        ))?;
+        Ok(nsent as usize)
     }
 }
 
 #[cfg(linux_raw)]
-fn _connect_un(sockfd: BorrowedFd<'_>, addr: &SocketAddrUnix) -> io::Result<()> {
-    crate::linux_raw::connect_un(sockfd, addr)
+fn _sendto_un(
+    sockfd: BorrowedFd<'_>,
+    buf: &[u8],
+    flags: SendFlags,
+    addr: &SocketAddrUnix,
+) -> io::Result<usize> {
+    crate::linux_raw::sendto_un(sockfd, buf, flags.bits(), addr)
 }
 
-/// `connect(sockfd, addr, sizeof(struct sockaddr_in))`
+/// `sendto(fd, buf, flags, addr, sizeof(struct sockaddr_in))`
 #[inline]
-pub fn connect_in<'f, Fd: AsFd<'f>>(sockfd: Fd, addr: &SocketAddrV4) -> io::Result<()> {
+pub fn sendto_in<'f, Fd: AsFd<'f>>(
+    sockfd: Fd,
+    buf: &[u8],
+    flags: SendFlags,
+    addr: &SocketAddrV4,
+) -> io::Result<usize> {
     let sockfd = sockfd.as_fd();
-    _connect_in(sockfd, addr)
+    _sendto_in(sockfd, buf, flags, addr)
 }
 
 #[cfg(libc)]
-fn _connect_in(sockfd: BorrowedFd<'_>, addr: &SocketAddrV4) -> io::Result<()> {
+fn _sendto_in(
+    sockfd: BorrowedFd<'_>,
+    buf: &[u8],
+    flags: SendFlags,
+    addr: &SocketAddrV4,
+) -> io::Result<usize> {
     unsafe {
-        zero_ok(libc::connect(
+        let nsent = negone_err(libc::sendto(
             sockfd.as_raw_fd(),
+            buf.as_ptr().cast(),
+            buf.len(),
+            flags.bits(),
             addr as *const _ as *const _,
             size_of::<SocketAddrV4>() as socklen_t,
-        ))
+        ))?;
+        Ok(nsent as usize)
     }
 }
 
 #[cfg(linux_raw)]
-fn _connect_in(sockfd: BorrowedFd<'_>, addr: &SocketAddrV4) -> io::Result<()> {
-    crate::linux_raw::connect_in(sockfd, addr)
+fn _sendto_in(
+    sockfd: BorrowedFd<'_>,
+    buf: &[u8],
+    flags: SendFlags,
+    addr: &SocketAddrV4,
+) -> io::Result<usize> {
+    crate::linux_raw::sendto_in(sockfd, buf, flags.bits(), addr)
 }
 
-/// `connect(sockfd, addr, sizeof(struct sockaddr_in6))`
+/// `sendto(fd, buf, flags, addr, sizeof(struct sockaddr_in6))`
 #[inline]
-pub fn connect_in6<'f, Fd: AsFd<'f>>(sockfd: Fd, addr: &SocketAddrV6) -> io::Result<()> {
+pub fn sendto_in6<'f, Fd: AsFd<'f>>(
+    sockfd: Fd,
+    buf: &[u8],
+    flags: SendFlags,
+    addr: &SocketAddrV6,
+) -> io::Result<usize> {
     let sockfd = sockfd.as_fd();
-    _connect_in6(sockfd, addr)
+    _sendto_in6(sockfd, buf, flags, addr)
 }
 
 #[cfg(libc)]
-fn _connect_in6(sockfd: BorrowedFd<'_>, addr: &SocketAddrV6) -> io::Result<()> {
+fn _sendto_in6(
+    sockfd: BorrowedFd<'_>,
+    buf: &[u8],
+    flags: SendFlags,
+    addr: &SocketAddrV6,
+) -> io::Result<usize> {
     unsafe {
-        zero_ok(libc::connect(
+        let nsent = negone_err(libc::sendto(
             sockfd.as_raw_fd(),
+            buf.as_ptr().cast(),
+            buf.len(),
+            flags.bits(),
             addr as *const _ as *const _,
             size_of::<SocketAddrV6>() as socklen_t,
-        ))
+        ))?;
+        Ok(nsent as usize)
     }
 }
 
 #[cfg(linux_raw)]
-fn _connect_in6(sockfd: BorrowedFd<'_>, addr: &SocketAddrV6) -> io::Result<()> {
-    crate::linux_raw::connect_in6(sockfd, addr)
+fn _sendto_in6(
+    sockfd: BorrowedFd<'_>,
+    buf: &[u8],
+    flags: SendFlags,
+    addr: &SocketAddrV6,
+) -> io::Result<usize> {
+    crate::linux_raw::sendto_in6(sockfd, buf, flags.bits(), addr)
 }
 
-/// `listen(fd, backlog)`
+/// `send(fd, buf, flags)`
 #[inline]
-pub fn listen<'f, Fd: AsFd<'f>>(sockfd: Fd, backlog: c_int) -> io::Result<()> {
+pub fn send<'f, Fd: AsFd<'f>>(sockfd: Fd, buf: &[u8], flags: SendFlags) -> io::Result<usize> {
     let sockfd = sockfd.as_fd();
-    _listen(sockfd, backlog)
+    _send(sockfd, buf, flags)
 }
 
 #[cfg(libc)]
-fn _listen(sockfd: BorrowedFd<'_>, backlog: c_int) -> io::Result<()> {
-    unsafe { zero_ok(libc::listen(sockfd.as_raw_fd(), backlog)) }
+fn _send(sockfd: BorrowedFd<'_>, buf: &[u8], flags: SendFlags) -> io::Result<usize> {
+    unsafe {
+        let nsent = negone_err(libc::send(
+            sockfd.as_raw_fd(),
+            buf.as_ptr().cast(),
+            buf.len(),
+            flags.bits(),
+        ))?;
+        Ok(nsent as usize)
+    }
 }
 
 #[cfg(linux_raw)]
-#[inline]
-fn _listen(sockfd: BorrowedFd<'_>, backlog: c_int) -> io::Result<()> {
-    crate::linux_raw::listen(sockfd, backlog)
+fn _send(sockfd: BorrowedFd<'_>, buf: &[u8], flags: SendFlags) -> io::Result<usize> {
+    crate::linux_raw::send(sockfd, buf, flags.bits())
 }
 
-/// `accept(fd, addr, len)`
-#[cfg(any(target_os = "ios", target_os = "macos"))]
+/// `recvfrom(fd, buf, len, flags, addr, len)`
+///
+/// The source address is returned as a [`SocketAddrStorage`] so that packet
+/// and vsock peers are representable alongside IP and unix ones; call
+/// [`SocketAddrStorage::decode`] for a `SocketAddr`.
 #[inline]
-pub fn accept<'f, Fd: AsFd<'f>>(sockfd: Fd) -> io::Result<(OwnedFd, SocketAddr)> {
+pub fn recvfrom<'f, Fd: AsFd<'f>>(
+    sockfd: Fd,
+    buf: &mut [MaybeUninit<u8>],
+    flags: RecvFlags,
+) -> io::Result<(usize, SocketAddrStorage)> {
     let sockfd = sockfd.as_fd();
-    _accept(sockfd)
+    _recvfrom(sockfd, buf, flags)
 }
 
-#[cfg(all(libc, any(target_os = "ios", target_os = "macos")))]
-fn _accept(sockfd: BorrowedFd<'_>) -> io::Result<(OwnedFd, SocketAddr)> {
+#[cfg(libc)]
+fn _recvfrom(
+    sockfd: BorrowedFd<'_>,
+    buf: &mut [MaybeUninit<u8>],
+    flags: RecvFlags,
+) -> io::Result<(usize, SocketAddrStorage)> {
     unsafe {
         let mut storage = MaybeUninit::<sockaddr_storage>::uninit();
         let mut len = size_of::<sockaddr_storage>() as socklen_t;
-        let raw_fd = negone_err(libc::accept(
+        let nread = negone_err(libc::recvfrom(
             sockfd.as_raw_fd(),
+            buf.as_mut_ptr().cast(),
+            buf.len(),
+            flags.bits(),
             storage.as_mut_ptr() as *mut _,
             &mut len,
         ))?;
-        let owned_fd = OwnedFd::from_raw_fd(raw_fd);
-        let storage = storage.assume_init();
-        let addr = match i32::from(storage.ss_family) {
-            libc::AF_INET => {
-                assert!(len as usize >= size_of::<SocketAddrV4>());
-                SocketAddr::V4((*(&storage as *const _ as *const SocketAddrV4)).clone())
-            }
-            libc::AF_INET6 => {
-                assert!(len as usize >= size_of::<SocketAddrV6>());
-                SocketAddr::V6((*(&storage as *const _ as *const SocketAddrV6)).clone())
-            }
-            libc::AF_LOCAL => {
-                assert!(len as usize >= size_of::<SocketAddrUnix>());
-                SocketAddr::Unix((*(&storage as *const _ as *const SocketAddrUnix)).clone())
-            }
-            _ => panic!(),
-        };
-        Ok((owned_fd, addr))
+        Ok((nread as usize, SocketAddrStorage::new(storage.assume_init(), len)))
     }
 }
 
-/// `accept4(fd, addr, len, flags)`
-#[cfg(not(any(target_os = "ios", target_os = "macos")))]
+#[cfg(linux_raw)]
+fn _recvfrom(
+    sockfd: BorrowedFd<'_>,
+    buf: &mut [MaybeUninit<u8>],
+    flags: RecvFlags,
+) -> io::Result<(usize, SocketAddrStorage)> {
+    let (nread, storage, len) = crate::linux_raw::recvfrom(sockfd, buf, flags.bits())?;
+    Ok((nread, SocketAddrStorage::new(storage, len)))
+}
+
+/// `recv(fd, buf, len, flags)`
 #[inline]
-pub fn accept4<'f, Fd: AsFd<'f>>(
+pub fn recv<'f, Fd: AsFd<'f>>(
     sockfd: Fd,
-    flags: AcceptFlags,
-) -> io::Result<(OwnedFd, SocketAddr)> {
+    buf: &mut [MaybeUninit<u8>],
+    flags: RecvFlags,
+) -> io::Result<usize> {
     let sockfd = sockfd.as_fd();
-    _accept4(sockfd, flags)
+    _recv(sockfd, buf, flags)
 }
 
-#[cfg(all(libc, not(any(target_os = "ios", target_os = "macos"))))]
-fn _accept4(sockfd: BorrowedFd<'_>, flags: AcceptFlags) -> io::Result<(OwnedFd, SocketAddr)> {
+#[cfg(libc)]
+fn _recv(
+    sockfd: BorrowedFd<'_>,
+    buf: &mut [MaybeUninit<u8>],
+    flags: RecvFlags,
+) -> io::Result<usize> {
     unsafe {
-        let mut storage = MaybeUninit::<sockaddr_storage>::uninit();
-        let mut len = size_of::<sockaddr_storage>() as socklen_t;
-        let raw_fd = negone_err(libc::accept4(
+        let nread = negone_err(libc::recv(
             sockfd.as_raw_fd(),
-            storage.as_mut_ptr() as *mut _,
-            &mut len,
+            buf.as_mut_ptr().cast(),
+            buf.len(),
             flags.bits(),
         ))?;
-        let owned_fd = OwnedFd::from_raw_fd(raw_fd);
-        let storage = storage.assume_init();
-        let addr = match i32::from(storage.ss_family) {
-            libc::AF_INET => {
-                assert!(len as usize >= size_of::<SocketAddrV4>());
-                SocketAddr::V4((*(&storage as *const _ as *const SocketAddrV4)).clone())
-            }
-            libc::AF_INET6 => {
-                assert!(len as usize >= size_of::<SocketAddrV6>());
-                SocketAddr::V6((*(&storage as *const _ as *const SocketAddrV6)).clone())
-            }
-            libc::AF_LOCAL => {
-                assert!(len as usize >= size_of::<SocketAddrUnix>());
-                SocketAddr::Unix((*(&storage as *const _ as *const SocketAddrUnix)).clone())
-            }
-            _ => panic!(),
-        };
-        Ok((owned_fd, addr))
+        Ok(nread as usize)
     }
 }
 
 #[cfg(linux_raw)]
-#[inline]
-fn _accept4(sockfd: BorrowedFd<'_>, flags: AcceptFlags) -> io::Result<(OwnedFd, SocketAddr)> {
-    let (owned_fd, storage, len) = crate::linux_raw::accept4(sockfd, flags.bits())?;
-    let addr = unsafe {
-        match u32::from(storage.ss_family) {
-            linux_raw_sys::general::AF_INET => {
-                assert!(len as usize >= size_of::<SocketAddrV4>());
-                SocketAddr::V4((*(&storage as *const _ as *const SocketAddrV4)).clone())
-            }
-            linux_raw_sys::general::AF_INET6 => {
-                assert!(len as usize >= size_of::<SocketAddrV6>());
-                SocketAddr::V6((*(&storage as *const _ as *const SocketAddrV6)).clone())
-            }
-            linux_raw_sys::general::AF_LOCAL => {
-                assert!(len as usize >= size_of::<SocketAddrUnix>());
-                SocketAddr::Unix((*(&storage as *const _ as *const SocketAddrUnix)).clone())
-            }
-            _ => panic!(),
-        }
-    };
-    Ok((owned_fd, addr))
+fn _recv(
+    sockfd: BorrowedFd<'_>,
+    buf: &mut [MaybeUninit<u8>],
+    flags: RecvFlags,
+) -> io::Result<usize> {
+    crate::linux_raw::recv(sockfd, buf, flags.bits())
 }
 
 /// `shutdown(fd, how)`
@@ -613,44 +1238,665 @@ pub fn socket_type<'f, Fd: AsFd<'f>>(fd: Fd) -> io::Result<SocketType> {
 
 #[cfg(libc)]
 fn _socket_type(fd: BorrowedFd<'_>) -> io::Result<SocketType> {
-    let mut buffer = MaybeUninit::<SocketType>::uninit();
-    let mut out_len = size_of::<SocketType>() as socklen_t;
-    unsafe {
-        zero_ok(libc::getsockopt(
-            fd.as_raw_fd(),
-            libc::SOL_SOCKET,
-            libc::SO_TYPE,
-            buffer.as_mut_ptr().cast::<libc::c_void>(),
-            &mut out_len,
-        ))?;
-        assert_eq!(
-            out_len as usize,
-            size_of::<SocketType>(),
-            "unexpected SocketType size"
-        );
-        Ok(buffer.assume_init())
-    }
+    unsafe { getsockopt(fd, libc::SOL_SOCKET, libc::SO_TYPE) }
 }
 
 #[cfg(linux_raw)]
 fn _socket_type(fd: BorrowedFd<'_>) -> io::Result<SocketType> {
     unsafe {
-        let mut buffer = MaybeUninit::<SocketType>::uninit();
-        let mut out_len = size_of::<SocketType>() as linux_raw_sys::general::socklen_t;
-        let slice =
-            std::slice::from_raw_parts_mut(buffer.as_mut_ptr() as *mut u8, size_of::<SocketType>());
-        crate::linux_raw::getsockopt(
+        getsockopt(
             fd,
-            linux_raw_sys::general::SOL_SOCKET as i32,
-            linux_raw_sys::general::SO_TYPE as i32,
-            slice,
-            &mut out_len,
-        )?;
-        assert_eq!(
-            out_len as usize,
-            size_of::<SocketType>(),
-            "unexpected SocketType size"
+            linux_raw_sys::general::SOL_SOCKET as c_int,
+            linux_raw_sys::general::SO_TYPE as c_int,
+        )
+    }
+}
+
+/// The generic `getsockopt(fd, level, optname)` core: read an option of type
+/// `T` into a fresh buffer, asserting the kernel filled exactly `size_of::<T>`
+/// bytes.
+///
+/// # Safety
+///
+/// `T` must be a type for which any bit pattern the kernel writes for
+/// `(level, optname)` is a valid value.
+#[cfg(libc)]
+unsafe fn getsockopt<T>(fd: BorrowedFd<'_>, level: c_int, optname: c_int) -> io::Result<T> {
+    let mut value = MaybeUninit::<T>::uninit();
+    let mut out_len = size_of::<T>() as socklen_t;
+    zero_ok(libc::getsockopt(
+        fd.as_raw_fd(),
+        level,
+        optname,
+        value.as_mut_ptr().cast::<libc::c_void>(),
+        &mut out_len,
+    ))?;
+    assert_eq!(out_len as usize, size_of::<T>(), "unexpected getsockopt size");
+    Ok(value.assume_init())
+}
+
+/// See the `libc` implementation for the contract and safety requirements.
+#[cfg(linux_raw)]
+unsafe fn getsockopt<T>(fd: BorrowedFd<'_>, level: c_int, optname: c_int) -> io::Result<T> {
+    let mut value = MaybeUninit::<T>::uninit();
+    let mut out_len = size_of::<T>() as socklen_t;
+    let slice = std::slice::from_raw_parts_mut(value.as_mut_ptr() as *mut u8, size_of::<T>());
+    crate::linux_raw::getsockopt(fd, level, optname, slice, &mut out_len)?;
+    assert_eq!(out_len as usize, size_of::<T>(), "unexpected getsockopt size");
+    Ok(value.assume_init())
+}
+
+/// The generic `setsockopt(fd, level, optname, value)` core.
+///
+/// # Safety
+///
+/// `T` must be the type the kernel expects for `(level, optname)`.
+#[cfg(libc)]
+unsafe fn setsockopt<T>(fd: BorrowedFd<'_>, level: c_int, optname: c_int, value: T) -> io::Result<()> {
+    zero_ok(libc::setsockopt(
+        fd.as_raw_fd(),
+        level,
+        optname,
+        &value as *const T as *const libc::c_void,
+        size_of::<T>() as socklen_t,
+    ))
+}
+
+/// See the `libc` implementation for the contract and safety requirements.
+#[cfg(linux_raw)]
+unsafe fn setsockopt<T>(fd: BorrowedFd<'_>, level: c_int, optname: c_int, value: T) -> io::Result<()> {
+    let slice = std::slice::from_raw_parts(&value as *const T as *const u8, size_of::<T>());
+    crate::linux_raw::setsockopt(fd, level, optname, slice)
+}
+
+/// Read a boolean socket option, encoded by the kernel as a nonzero `c_int`.
+#[inline]
+fn getsockopt_bool(fd: BorrowedFd<'_>, level: c_int, optname: c_int) -> io::Result<bool> {
+    let value: c_int = unsafe { getsockopt(fd, level, optname)? };
+    Ok(value != 0)
+}
+
+/// Write a boolean socket option as a `c_int`.
+#[inline]
+fn setsockopt_bool(
+    fd: BorrowedFd<'_>,
+    level: c_int,
+    optname: c_int,
+    value: bool,
+) -> io::Result<()> {
+    unsafe { setsockopt(fd, level, optname, value as c_int) }
+}
+
+/// Read a `c_int`-valued socket option.
+#[inline]
+fn getsockopt_int(fd: BorrowedFd<'_>, level: c_int, optname: c_int) -> io::Result<c_int> {
+    unsafe { getsockopt(fd, level, optname) }
+}
+
+/// Write a `c_int`-valued socket option.
+#[inline]
+fn setsockopt_int(fd: BorrowedFd<'_>, level: c_int, optname: c_int, value: c_int) -> io::Result<()> {
+    unsafe { setsockopt(fd, level, optname, value) }
+}
+
+/// Decode a `timeval` socket option into a `Duration`, treating all-zero as
+/// "no timeout".
+fn timeval_to_duration(tv: c::timeval) -> Option<Duration> {
+    if tv.tv_sec == 0 && tv.tv_usec == 0 {
+        None
+    } else {
+        Some(Duration::new(tv.tv_sec as u64, (tv.tv_usec as u32) * 1000))
+    }
+}
+
+/// Encode a `Duration` (or "no timeout") into a `timeval`.
+fn duration_to_timeval(timeout: Option<Duration>) -> c::timeval {
+    match timeout {
+        None => c::timeval {
+            tv_sec: 0,
+            tv_usec: 0,
+        },
+        Some(timeout) => c::timeval {
+            tv_sec: timeout.as_secs() as _,
+            tv_usec: timeout.subsec_micros() as _,
+        },
+    }
+}
+
+/// `getsockopt(fd, SOL_SOCKET, SO_REUSEADDR)`
+#[inline]
+pub fn get_reuseaddr<'f, Fd: AsFd<'f>>(fd: Fd) -> io::Result<bool> {
+    getsockopt_bool(fd.as_fd(), c::SOL_SOCKET as c_int, c::SO_REUSEADDR as c_int)
+}
+
+/// `setsockopt(fd, SOL_SOCKET, SO_REUSEADDR, value)`
+#[inline]
+pub fn set_reuseaddr<'f, Fd: AsFd<'f>>(fd: Fd, value: bool) -> io::Result<()> {
+    setsockopt_bool(
+        fd.as_fd(),
+        c::SOL_SOCKET as c_int,
+        c::SO_REUSEADDR as c_int,
+        value,
+    )
+}
+
+/// `getsockopt(fd, SOL_SOCKET, SO_REUSEPORT)`
+#[inline]
+pub fn get_reuseport<'f, Fd: AsFd<'f>>(fd: Fd) -> io::Result<bool> {
+    getsockopt_bool(fd.as_fd(), c::SOL_SOCKET as c_int, c::SO_REUSEPORT as c_int)
+}
+
+/// `setsockopt(fd, SOL_SOCKET, SO_REUSEPORT, value)`
+#[inline]
+pub fn set_reuseport<'f, Fd: AsFd<'f>>(fd: Fd, value: bool) -> io::Result<()> {
+    setsockopt_bool(
+        fd.as_fd(),
+        c::SOL_SOCKET as c_int,
+        c::SO_REUSEPORT as c_int,
+        value,
+    )
+}
+
+/// `getsockopt(fd, SOL_SOCKET, SO_BROADCAST)`
+#[inline]
+pub fn get_broadcast<'f, Fd: AsFd<'f>>(fd: Fd) -> io::Result<bool> {
+    getsockopt_bool(fd.as_fd(), c::SOL_SOCKET as c_int, c::SO_BROADCAST as c_int)
+}
+
+/// `setsockopt(fd, SOL_SOCKET, SO_BROADCAST, value)`
+#[inline]
+pub fn set_broadcast<'f, Fd: AsFd<'f>>(fd: Fd, value: bool) -> io::Result<()> {
+    setsockopt_bool(
+        fd.as_fd(),
+        c::SOL_SOCKET as c_int,
+        c::SO_BROADCAST as c_int,
+        value,
+    )
+}
+
+/// `getsockopt(fd, SOL_SOCKET, SO_KEEPALIVE)`
+#[inline]
+pub fn get_keepalive<'f, Fd: AsFd<'f>>(fd: Fd) -> io::Result<bool> {
+    getsockopt_bool(fd.as_fd(), c::SOL_SOCKET as c_int, c::SO_KEEPALIVE as c_int)
+}
+
+/// `setsockopt(fd, SOL_SOCKET, SO_KEEPALIVE, value)`
+#[inline]
+pub fn set_keepalive<'f, Fd: AsFd<'f>>(fd: Fd, value: bool) -> io::Result<()> {
+    setsockopt_bool(
+        fd.as_fd(),
+        c::SOL_SOCKET as c_int,
+        c::SO_KEEPALIVE as c_int,
+        value,
+    )
+}
+
+/// `getsockopt(fd, SOL_SOCKET, SO_RCVBUF)`
+#[inline]
+pub fn get_recv_buffer_size<'f, Fd: AsFd<'f>>(fd: Fd) -> io::Result<usize> {
+    Ok(getsockopt_int(fd.as_fd(), c::SOL_SOCKET as c_int, c::SO_RCVBUF as c_int)? as usize)
+}
+
+/// `setsockopt(fd, SOL_SOCKET, SO_RCVBUF, size)`
+///
+/// `size` is clamped to `c_int::MAX`, the largest value the kernel's `int`
+/// argument can carry.
+#[inline]
+pub fn set_recv_buffer_size<'f, Fd: AsFd<'f>>(fd: Fd, size: usize) -> io::Result<()> {
+    setsockopt_int(
+        fd.as_fd(),
+        c::SOL_SOCKET as c_int,
+        c::SO_RCVBUF as c_int,
+        size.min(c_int::MAX as usize) as c_int,
+    )
+}
+
+/// `getsockopt(fd, SOL_SOCKET, SO_SNDBUF)`
+#[inline]
+pub fn get_send_buffer_size<'f, Fd: AsFd<'f>>(fd: Fd) -> io::Result<usize> {
+    Ok(getsockopt_int(fd.as_fd(), c::SOL_SOCKET as c_int, c::SO_SNDBUF as c_int)? as usize)
+}
+
+/// `setsockopt(fd, SOL_SOCKET, SO_SNDBUF, size)`
+///
+/// `size` is clamped to `c_int::MAX`, the largest value the kernel's `int`
+/// argument can carry.
+#[inline]
+pub fn set_send_buffer_size<'f, Fd: AsFd<'f>>(fd: Fd, size: usize) -> io::Result<()> {
+    setsockopt_int(
+        fd.as_fd(),
+        c::SOL_SOCKET as c_int,
+        c::SO_SNDBUF as c_int,
+        size.min(c_int::MAX as usize) as c_int,
+    )
+}
+
+/// `getsockopt(fd, SOL_SOCKET, SO_ERROR)`
+#[inline]
+pub fn get_error<'f, Fd: AsFd<'f>>(fd: Fd) -> io::Result<c_int> {
+    getsockopt_int(fd.as_fd(), c::SOL_SOCKET as c_int, c::SO_ERROR as c_int)
+}
+
+/// `getsockopt(fd, SOL_SOCKET, SO_LINGER)`
+#[inline]
+pub fn get_linger<'f, Fd: AsFd<'f>>(fd: Fd) -> io::Result<Option<Duration>> {
+    let linger: c::linger =
+        unsafe { getsockopt(fd.as_fd(), c::SOL_SOCKET as c_int, c::SO_LINGER as c_int)? };
+    Ok(if linger.l_onoff != 0 {
+        Some(Duration::from_secs(linger.l_linger as u64))
+    } else {
+        None
+    })
+}
+
+/// `setsockopt(fd, SOL_SOCKET, SO_LINGER, linger)`
+#[inline]
+pub fn set_linger<'f, Fd: AsFd<'f>>(fd: Fd, linger: Option<Duration>) -> io::Result<()> {
+    let linger = c::linger {
+        l_onoff: linger.is_some() as c_int,
+        l_linger: linger.unwrap_or_default().as_secs() as c_int,
+    };
+    unsafe {
+        setsockopt(
+            fd.as_fd(),
+            c::SOL_SOCKET as c_int,
+            c::SO_LINGER as c_int,
+            linger,
+        )
+    }
+}
+
+/// `getsockopt(fd, SOL_SOCKET, SO_RCVTIMEO)`
+#[inline]
+pub fn get_recv_timeout<'f, Fd: AsFd<'f>>(fd: Fd) -> io::Result<Option<Duration>> {
+    let tv: c::timeval =
+        unsafe { getsockopt(fd.as_fd(), c::SOL_SOCKET as c_int, c::SO_RCVTIMEO as c_int)? };
+    Ok(timeval_to_duration(tv))
+}
+
+/// `setsockopt(fd, SOL_SOCKET, SO_RCVTIMEO, timeout)`
+#[inline]
+pub fn set_recv_timeout<'f, Fd: AsFd<'f>>(fd: Fd, timeout: Option<Duration>) -> io::Result<()> {
+    unsafe {
+        setsockopt(
+            fd.as_fd(),
+            c::SOL_SOCKET as c_int,
+            c::SO_RCVTIMEO as c_int,
+            duration_to_timeval(timeout),
+        )
+    }
+}
+
+/// `getsockopt(fd, SOL_SOCKET, SO_SNDTIMEO)`
+#[inline]
+pub fn get_send_timeout<'f, Fd: AsFd<'f>>(fd: Fd) -> io::Result<Option<Duration>> {
+    let tv: c::timeval =
+        unsafe { getsockopt(fd.as_fd(), c::SOL_SOCKET as c_int, c::SO_SNDTIMEO as c_int)? };
+    Ok(timeval_to_duration(tv))
+}
+
+/// `setsockopt(fd, SOL_SOCKET, SO_SNDTIMEO, timeout)`
+#[inline]
+pub fn set_send_timeout<'f, Fd: AsFd<'f>>(fd: Fd, timeout: Option<Duration>) -> io::Result<()> {
+    unsafe {
+        setsockopt(
+            fd.as_fd(),
+            c::SOL_SOCKET as c_int,
+            c::SO_SNDTIMEO as c_int,
+            duration_to_timeval(timeout),
+        )
+    }
+}
+
+/// `getsockopt(fd, IPPROTO_TCP, TCP_NODELAY)`
+#[inline]
+pub fn get_tcp_nodelay<'f, Fd: AsFd<'f>>(fd: Fd) -> io::Result<bool> {
+    getsockopt_bool(fd.as_fd(), c::IPPROTO_TCP as c_int, c::TCP_NODELAY as c_int)
+}
+
+/// `setsockopt(fd, IPPROTO_TCP, TCP_NODELAY, value)`
+#[inline]
+pub fn set_tcp_nodelay<'f, Fd: AsFd<'f>>(fd: Fd, value: bool) -> io::Result<()> {
+    setsockopt_bool(
+        fd.as_fd(),
+        c::IPPROTO_TCP as c_int,
+        c::TCP_NODELAY as c_int,
+        value,
+    )
+}
+
+/// `getsockopt(fd, IPPROTO_TCP, TCP_KEEPIDLE)`
+#[inline]
+pub fn get_tcp_keepidle<'f, Fd: AsFd<'f>>(fd: Fd) -> io::Result<u32> {
+    Ok(getsockopt_int(fd.as_fd(), c::IPPROTO_TCP as c_int, c::TCP_KEEPIDLE as c_int)? as u32)
+}
+
+/// `setsockopt(fd, IPPROTO_TCP, TCP_KEEPIDLE, secs)`
+#[inline]
+pub fn set_tcp_keepidle<'f, Fd: AsFd<'f>>(fd: Fd, secs: u32) -> io::Result<()> {
+    setsockopt_int(
+        fd.as_fd(),
+        c::IPPROTO_TCP as c_int,
+        c::TCP_KEEPIDLE as c_int,
+        secs as c_int,
+    )
+}
+
+/// `getsockopt(fd, IPPROTO_TCP, TCP_KEEPINTVL)`
+#[inline]
+pub fn get_tcp_keepintvl<'f, Fd: AsFd<'f>>(fd: Fd) -> io::Result<u32> {
+    Ok(getsockopt_int(fd.as_fd(), c::IPPROTO_TCP as c_int, c::TCP_KEEPINTVL as c_int)? as u32)
+}
+
+/// `setsockopt(fd, IPPROTO_TCP, TCP_KEEPINTVL, secs)`
+#[inline]
+pub fn set_tcp_keepintvl<'f, Fd: AsFd<'f>>(fd: Fd, secs: u32) -> io::Result<()> {
+    setsockopt_int(
+        fd.as_fd(),
+        c::IPPROTO_TCP as c_int,
+        c::TCP_KEEPINTVL as c_int,
+        secs as c_int,
+    )
+}
+
+/// `getsockopt(fd, IPPROTO_TCP, TCP_KEEPCNT)`
+#[inline]
+pub fn get_tcp_keepcnt<'f, Fd: AsFd<'f>>(fd: Fd) -> io::Result<u32> {
+    Ok(getsockopt_int(fd.as_fd(), c::IPPROTO_TCP as c_int, c::TCP_KEEPCNT as c_int)? as u32)
+}
+
+/// `setsockopt(fd, IPPROTO_TCP, TCP_KEEPCNT, count)`
+#[inline]
+pub fn set_tcp_keepcnt<'f, Fd: AsFd<'f>>(fd: Fd, count: u32) -> io::Result<()> {
+    setsockopt_int(
+        fd.as_fd(),
+        c::IPPROTO_TCP as c_int,
+        c::TCP_KEEPCNT as c_int,
+        count as c_int,
+    )
+}
+
+/// `getsockopt(fd, IPPROTO_IP, IP_TTL)`
+#[inline]
+pub fn get_ip_ttl<'f, Fd: AsFd<'f>>(fd: Fd) -> io::Result<u32> {
+    Ok(getsockopt_int(fd.as_fd(), c::IPPROTO_IP as c_int, c::IP_TTL as c_int)? as u32)
+}
+
+/// `setsockopt(fd, IPPROTO_IP, IP_TTL, ttl)`
+#[inline]
+pub fn set_ip_ttl<'f, Fd: AsFd<'f>>(fd: Fd, ttl: u32) -> io::Result<()> {
+    setsockopt_int(
+        fd.as_fd(),
+        c::IPPROTO_IP as c_int,
+        c::IP_TTL as c_int,
+        ttl as c_int,
+    )
+}
+
+/// `getsockopt(fd, IPPROTO_IPV6, IPV6_V6ONLY)`
+#[inline]
+pub fn get_ipv6_v6only<'f, Fd: AsFd<'f>>(fd: Fd) -> io::Result<bool> {
+    getsockopt_bool(fd.as_fd(), c::IPPROTO_IPV6 as c_int, c::IPV6_V6ONLY as c_int)
+}
+
+/// `setsockopt(fd, IPPROTO_IPV6, IPV6_V6ONLY, value)`
+#[inline]
+pub fn set_ipv6_v6only<'f, Fd: AsFd<'f>>(fd: Fd, value: bool) -> io::Result<()> {
+    setsockopt_bool(
+        fd.as_fd(),
+        c::IPPROTO_IPV6 as c_int,
+        c::IPV6_V6ONLY as c_int,
+        value,
+    )
+}
+
+/// `setsockopt(fd, IPPROTO_IP, IP_ADD_MEMBERSHIP, mreq)`
+#[inline]
+pub fn set_ip_add_membership<'f, Fd: AsFd<'f>>(
+    fd: Fd,
+    multiaddr: &Ipv4Addr,
+    interface: &Ipv4Addr,
+) -> io::Result<()> {
+    let mreq = ip_mreq(multiaddr, interface);
+    unsafe {
+        setsockopt(
+            fd.as_fd(),
+            c::IPPROTO_IP as c_int,
+            c::IP_ADD_MEMBERSHIP as c_int,
+            mreq,
+        )
+    }
+}
+
+/// `setsockopt(fd, IPPROTO_IP, IP_DROP_MEMBERSHIP, mreq)`
+#[inline]
+pub fn set_ip_drop_membership<'f, Fd: AsFd<'f>>(
+    fd: Fd,
+    multiaddr: &Ipv4Addr,
+    interface: &Ipv4Addr,
+) -> io::Result<()> {
+    let mreq = ip_mreq(multiaddr, interface);
+    unsafe {
+        setsockopt(
+            fd.as_fd(),
+            c::IPPROTO_IP as c_int,
+            c::IP_DROP_MEMBERSHIP as c_int,
+            mreq,
+        )
+    }
+}
+
+/// `setsockopt(fd, IPPROTO_IPV6, IPV6_ADD_MEMBERSHIP, mreq)`
+#[inline]
+pub fn set_ipv6_add_membership<'f, Fd: AsFd<'f>>(
+    fd: Fd,
+    multiaddr: &Ipv6Addr,
+    interface: u32,
+) -> io::Result<()> {
+    let mreq = ipv6_mreq(multiaddr, interface);
+    unsafe {
+        setsockopt(
+            fd.as_fd(),
+            c::IPPROTO_IPV6 as c_int,
+            c::IPV6_ADD_MEMBERSHIP as c_int,
+            mreq,
+        )
+    }
+}
+
+/// `setsockopt(fd, IPPROTO_IPV6, IPV6_DROP_MEMBERSHIP, mreq)`
+#[inline]
+pub fn set_ipv6_drop_membership<'f, Fd: AsFd<'f>>(
+    fd: Fd,
+    multiaddr: &Ipv6Addr,
+    interface: u32,
+) -> io::Result<()> {
+    let mreq = ipv6_mreq(multiaddr, interface);
+    unsafe {
+        setsockopt(
+            fd.as_fd(),
+            c::IPPROTO_IPV6 as c_int,
+            c::IPV6_DROP_MEMBERSHIP as c_int,
+            mreq,
+        )
+    }
+}
+
+/// Build an `ip_mreq` from a multicast group and a local interface address.
+fn ip_mreq(multiaddr: &Ipv4Addr, interface: &Ipv4Addr) -> c::ip_mreq {
+    c::ip_mreq {
+        imr_multiaddr: c::in_addr {
+            s_addr: u32::from_ne_bytes(multiaddr.octets()),
+        },
+        imr_interface: c::in_addr {
+            s_addr: u32::from_ne_bytes(interface.octets()),
+        },
+    }
+}
+
+/// Build an `ipv6_mreq` from a multicast group and an interface index.
+fn ipv6_mreq(multiaddr: &Ipv6Addr, interface: u32) -> c::ipv6_mreq {
+    // `in6_addr` exposes its bytes under different field names across the two
+    // backends (`s6_addr` in libc, the `in6_u` union in `linux_raw_sys`), so
+    // start from a zeroed struct and copy the octets in by raw pointer.
+    let mut addr: c::in6_addr = unsafe { std::mem::zeroed() };
+    unsafe {
+        core::ptr::copy_nonoverlapping(
+            multiaddr.octets().as_ptr(),
+            &mut addr as *mut c::in6_addr as *mut u8,
+            16,
         );
-        Ok(buffer.assume_init())
+    }
+    c::ipv6_mreq {
+        ipv6mr_multiaddr: addr,
+        ipv6mr_interface: interface as _,
+    }
+}
+
+#[cfg(libc)]
+bitflags! {
+    /// Interface flags, as reported by `SIOCGIFFLAGS` and `getifaddrs`.
+    ///
+    /// Like the rest of the [`getifaddrs`] subsystem, this is only available
+    /// on the `libc` backend; the `linux_raw` backend has no `getifaddrs`.
+    pub struct InterfaceFlags: c_int {
+        /// `IFF_UP`
+        const UP = libc::IFF_UP;
+        /// `IFF_BROADCAST`
+        const BROADCAST = libc::IFF_BROADCAST;
+        /// `IFF_LOOPBACK`
+        const LOOPBACK = libc::IFF_LOOPBACK;
+        /// `IFF_POINTOPOINT`
+        const POINTOPOINT = libc::IFF_POINTOPOINT;
+        /// `IFF_RUNNING`
+        const RUNNING = libc::IFF_RUNNING;
+        /// `IFF_MULTICAST`
+        const MULTICAST = libc::IFF_MULTICAST;
+    }
+}
+
+/// A single interface address record, as returned by [`getifaddrs`].
+///
+/// Unlike the raw `ifaddrs` list it is built from, this owns its storage, so
+/// it outlives the `freeifaddrs` call.
+#[cfg(libc)]
+pub struct InterfaceAddress {
+    name: String,
+    flags: InterfaceFlags,
+    address: Option<SocketAddrStorage>,
+    netmask: Option<SocketAddrStorage>,
+    // The `ifa_ifu` union slot, reported as either a broadcast or a
+    // point-to-point destination address depending on the interface flags.
+    ifu: Option<SocketAddrStorage>,
+}
+
+#[cfg(libc)]
+impl InterfaceAddress {
+    /// The interface name, e.g. `"eth0"`.
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The interface flags.
+    #[inline]
+    pub fn flags(&self) -> InterfaceFlags {
+        self.flags
+    }
+
+    /// The interface address, if any.
+    #[inline]
+    pub fn address(&self) -> Option<&SocketAddrStorage> {
+        self.address.as_ref()
+    }
+
+    /// The netmask associated with the address, if any.
+    #[inline]
+    pub fn netmask(&self) -> Option<&SocketAddrStorage> {
+        self.netmask.as_ref()
+    }
+
+    /// The broadcast address, if this is a `BROADCAST` interface.
+    #[inline]
+    pub fn broadcast(&self) -> Option<&SocketAddrStorage> {
+        if self.flags.contains(InterfaceFlags::BROADCAST) {
+            self.ifu.as_ref()
+        } else {
+            None
+        }
+    }
+
+    /// The destination address, if this is a `POINTOPOINT` interface.
+    #[inline]
+    pub fn destination(&self) -> Option<&SocketAddrStorage> {
+        if self.flags.contains(InterfaceFlags::POINTOPOINT) {
+            self.ifu.as_ref()
+        } else {
+            None
+        }
+    }
+}
+
+/// An owning iterator over the interface addresses returned by [`getifaddrs`].
+#[cfg(libc)]
+pub struct Interfaces {
+    iter: std::vec::IntoIter<InterfaceAddress>,
+}
+
+#[cfg(libc)]
+impl Iterator for Interfaces {
+    type Item = InterfaceAddress;
+
+    #[inline]
+    fn next(&mut self) -> Option<InterfaceAddress> {
+        self.iter.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+/// `getifaddrs()`
+///
+/// This enumeration subsystem (along with [`InterfaceAddress`], [`Interfaces`],
+/// and [`InterfaceFlags`]) is provided only on the `libc` backend, which wraps
+/// the C library's `getifaddrs`/`freeifaddrs`; it is absent on the `linux_raw`
+/// backend.
+#[cfg(libc)]
+#[inline]
+pub fn getifaddrs() -> io::Result<Interfaces> {
+    _getifaddrs()
+}
+
+#[cfg(libc)]
+fn _getifaddrs() -> io::Result<Interfaces> {
+    unsafe {
+        let mut ifap: *mut libc::ifaddrs = std::ptr::null_mut();
+        zero_ok(libc::getifaddrs(&mut ifap))?;
+        let mut addrs = Vec::new();
+        let mut cur = ifap;
+        while !cur.is_null() {
+            let ifa = &*cur;
+            #[cfg(any(target_os = "android", target_os = "linux"))]
+            let dstaddr = ifa.ifa_ifu;
+            #[cfg(not(any(target_os = "android", target_os = "linux")))]
+            let dstaddr = ifa.ifa_dstaddr;
+            addrs.push(InterfaceAddress {
+                name: std::ffi::CStr::from_ptr(ifa.ifa_name)
+                    .to_string_lossy()
+                    .into_owned(),
+                flags: InterfaceFlags::from_bits_truncate(ifa.ifa_flags as c_int),
+                address: SocketAddrStorage::clone_raw(ifa.ifa_addr as *const sockaddr),
+                netmask: SocketAddrStorage::clone_raw(ifa.ifa_netmask as *const sockaddr),
+                ifu: SocketAddrStorage::clone_raw(dstaddr as *const sockaddr),
+            });
+            cur = ifa.ifa_next;
+        }
+        libc::freeifaddrs(ifap);
+        Ok(Interfaces {
+            iter: addrs.into_iter(),
+        })
     }
 }